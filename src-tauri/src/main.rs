@@ -1,45 +1,245 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use backtrace::Backtrace;
+use fern::colors::{Color, ColoredLevelConfig};
+use include_dir::{include_dir, Dir};
 use std::{
-    env,
-    fs::{self, OpenOptions},
-    io::Write as IoWrite,
+    env, fs,
+    io::{self, BufRead, BufReader, Write},
+    net::TcpStream,
     path::{Path, PathBuf},
     process::{Child, Command, Stdio},
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
     thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tauri::{Emitter, Manager, RunEvent, WindowEvent};
 
-type SharedChild = Arc<Mutex<Option<Child>>>;
+const CRASH_LOG_PREFIX: &str = "deadball-crash-";
+const CRASH_LOG_SUFFIX: &str = ".log";
 
-fn log_backend(msg: &str) {
-    if let Ok(mut f) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("/tmp/deadball-backend.log")
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+const BASE_BACKOFF_SECS: u64 = 1;
+const MAX_BACKOFF_SECS: u64 = 30;
+const HEALTHY_UPTIME_SECS: u64 = 10;
+const POLL_INTERVAL_MS: u64 = 500;
+const DEFAULT_BACKEND_HOST: &str = "127.0.0.1";
+const DEFAULT_BACKEND_PORT: u16 = 8000;
+const READY_PROBE_ATTEMPTS: u32 = 20;
+const READY_PROBE_INTERVAL_MS: u64 = 250;
+const LOG_FILE_NAME: &str = "deadball-backend.log";
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_LOG_FILES: u32 = 5;
+
+/// Rolls `deadball-backend.log` to `deadball-backend.1.log`, shifting older
+/// numbered files up and keeping at most `MAX_LOG_FILES` on disk.
+fn roll_log_files(log_dir: &Path) {
+    for i in (1..MAX_LOG_FILES).rev() {
+        let from = log_dir.join(format!("deadball-backend.{i}.log"));
+        let to = log_dir.join(format!("deadball-backend.{}.log", i + 1));
+        if from.exists() {
+            let _ = fs::rename(&from, &to);
+        }
+    }
+    let _ = fs::rename(
+        log_dir.join(LOG_FILE_NAME),
+        log_dir.join("deadball-backend.1.log"),
+    );
+}
+
+/// Rolls the log left over from a previous run if it's already past
+/// `MAX_LOG_BYTES` by the time this run starts.
+fn rotate_log_file(log_dir: &Path) {
+    let size = fs::metadata(log_dir.join(LOG_FILE_NAME))
+        .map(|m| m.len())
+        .unwrap_or(0);
+    if size >= MAX_LOG_BYTES {
+        roll_log_files(log_dir);
+    }
+}
+
+/// A `Write` sink for `fern` that rolls `deadball-backend.log` once it
+/// crosses `MAX_LOG_BYTES`, so a single long-running session still gets
+/// rotated rather than only across app restarts.
+struct RotatingFileWriter {
+    log_dir: PathBuf,
+    file: fs::File,
+    size: u64,
+}
+
+impl RotatingFileWriter {
+    fn open(log_dir: &Path) -> io::Result<Self> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_dir.join(LOG_FILE_NAME))?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            log_dir: log_dir.to_path_buf(),
+            file,
+            size,
+        })
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size >= MAX_LOG_BYTES {
+            roll_log_files(&self.log_dir);
+            self.file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.log_dir.join(LOG_FILE_NAME))?;
+            self.size = 0;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Wires up `log`/`fern`: colored level output on the console plus a
+/// rotating file sink under the OS app-log directory. The level is
+/// controlled by `DEADBALL_LOG` (falling back to `RUST_LOG`, then `info`).
+fn init_logging(app: &tauri::App) {
+    let log_dir = app.path().app_log_dir().unwrap_or_else(|_| env::temp_dir());
+    let _ = fs::create_dir_all(&log_dir);
+    rotate_log_file(&log_dir);
+
+    let level = env::var("DEADBALL_LOG")
+        .or_else(|_| env::var("RUST_LOG"))
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(log::LevelFilter::Info);
+
+    let colors = ColoredLevelConfig::new()
+        .info(Color::Green)
+        .warn(Color::Yellow)
+        .error(Color::Red);
+
+    let console = fern::Dispatch::new().format(move |out, message, record| {
+        out.finish(format_args!(
+            "[{}][{}] {}",
+            colors.color(record.level()),
+            record.target(),
+            message
+        ))
+    });
+
+    let file = fern::Dispatch::new()
+        .format(|out, message, record| {
+            out.finish(format_args!(
+                "[{}][{}] {}",
+                record.level(),
+                record.target(),
+                message
+            ))
+        })
+        .chain(Box::new(
+            RotatingFileWriter::open(&log_dir).expect("failed to open backend log file"),
+        ) as Box<dyn Write + Send>);
+
+    if let Err(err) = fern::Dispatch::new()
+        .level(level)
+        .chain(console.chain(std::io::stdout()))
+        .chain(file)
+        .apply()
     {
-        let _ = writeln!(f, "{}", msg);
+        eprintln!("Failed to initialize logging: {err}");
+    }
+}
+
+fn panic_payload_message(info: &std::panic::PanicHookInfo) -> String {
+    if let Some(s) = info.payload().downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = info.payload().downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
     }
 }
 
+/// Installs a panic hook that writes a timestamped crash report (payload +
+/// backtrace) into the app-log directory, logs it, kills the backend so it
+/// isn't orphaned, and then exits the process.
+fn install_panic_hook(log_dir: PathBuf, backend_slot: Arc<Mutex<Option<SharedBackend>>>) {
+    std::panic::set_hook(Box::new(move |info| {
+        let backtrace = Backtrace::new();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = log_dir.join(format!("{CRASH_LOG_PREFIX}{timestamp}{CRASH_LOG_SUFFIX}"));
+
+        let report = format!(
+            "panic: {}\nlocation: {}\n\nbacktrace:\n{:?}",
+            panic_payload_message(info),
+            info.location()
+                .map(|l| l.to_string())
+                .unwrap_or_else(|| "unknown".to_string()),
+            backtrace
+        );
+
+        if let Err(err) = fs::write(&path, &report) {
+            log::error!("Failed to write crash report to {}: {err}", path.display());
+        } else {
+            log::error!("Crash report written to {}", path.display());
+        }
+
+        if let Some(backend) = backend_slot.lock().unwrap().as_ref() {
+            backend.kill();
+        }
+
+        std::process::exit(1);
+    }));
+}
+
+/// Looks for a crash report left by a previous run and returns its path (the
+/// most recently modified one, if several are present).
+fn find_previous_crash(log_dir: &Path) -> Option<PathBuf> {
+    let entries = fs::read_dir(log_dir).ok()?;
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.starts_with(CRASH_LOG_PREFIX) && name.ends_with(CRASH_LOG_SUFFIX)
+                })
+        })
+        .max_by_key(|path| {
+            fs::metadata(path)
+                .and_then(|m| m.modified())
+                .unwrap_or(UNIX_EPOCH)
+        })
+}
+
 fn python_cmd(backend_dir: &Path) -> PathBuf {
     // Prefer project venvs if present, fallback to python3 in PATH.
     let repo_root_venv = backend_dir.join("../.venv/bin/python");
     if repo_root_venv.exists() {
-        log_backend(&format!(
+        log::info!(
             "Using repo root venv python at {}",
             repo_root_venv.display()
-        ));
+        );
         return repo_root_venv;
     }
 
     let backend_venv = backend_dir.join(".venv/bin/python");
     if backend_venv.exists() {
-        log_backend(&format!(
+        log::info!(
             "Using backend-local venv python at {}",
             backend_venv.display()
-        ));
+        );
         return backend_venv;
     }
 
@@ -48,47 +248,302 @@ fn python_cmd(backend_dir: &Path) -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("python3"))
 }
 
-fn spawn_backend(backend_dir: &Path) -> std::io::Result<Child> {
+/// Picks the host/port the backend will listen on: `DEADBALL_HOST`/
+/// `DEADBALL_PORT` win if set, otherwise the host defaults to loopback and a
+/// free port is found by binding to port 0.
+fn resolve_backend_address() -> (String, u16) {
+    let host = env::var("DEADBALL_HOST").unwrap_or_else(|_| DEFAULT_BACKEND_HOST.to_string());
+
+    if let Ok(raw_port) = env::var("DEADBALL_PORT") {
+        match raw_port.parse::<u16>() {
+            Ok(port) => return (host, port),
+            Err(err) => log::warn!("Ignoring invalid DEADBALL_PORT {raw_port:?}: {err}"),
+        }
+    }
+
+    let port = std::net::TcpListener::bind((host.as_str(), 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .unwrap_or_else(|err| {
+            log::warn!("Falling back to default port {DEFAULT_BACKEND_PORT}: {err}");
+            DEFAULT_BACKEND_PORT
+        });
+
+    (host, port)
+}
+
+/// Variables a spawned process needs to behave normally on this platform,
+/// mirroring Tauri's own `command_env` allowlist rather than a minimal
+/// Unix-shaped guess: on Windows this covers the paths Python/OpenSSL use to
+/// find the system, the user profile, and temp storage; on Unix it covers
+/// locale and temp dir in addition to `PATH`/`HOME`.
+#[cfg(windows)]
+const INHERITED_ENV_VARS: &[&str] = &[
+    "PATH",
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "WINDIR",
+    "COMSPEC",
+    "PATHEXT",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "USERPROFILE",
+    "TEMP",
+    "TMP",
+    "PROGRAMDATA",
+    "PROGRAMFILES",
+    "PROGRAMFILES(X86)",
+    "NUMBER_OF_PROCESSORS",
+    "PROCESSOR_ARCHITECTURE",
+];
+
+#[cfg(not(windows))]
+const INHERITED_ENV_VARS: &[&str] = &["PATH", "HOME", "LANG", "LC_ALL", "TMPDIR", "TERM"];
+
+/// A minimal, predictable environment for the spawned Python process:
+/// `INHERITED_ENV_VARS` plus `PYTHONPATH` pointed at the backend dir, rather
+/// than the full parent environment.
+fn backend_env(backend_dir: &Path) -> Vec<(String, String)> {
+    let mut env = Vec::new();
+    for var in INHERITED_ENV_VARS {
+        if let Ok(value) = env::var(var) {
+            env.push((var.to_string(), value));
+        }
+    }
+    env.push(("PYTHONPATH".to_string(), backend_dir.display().to_string()));
+    env
+}
+
+fn spawn_backend(backend_dir: &Path, host: &str, port: u16) -> std::io::Result<Child> {
     let mut cmd = Command::new(python_cmd(backend_dir));
-    log_backend(&format!("Spawning backend from dir {}", backend_dir.display()));
+    log::info!(
+        "Spawning backend from dir {} on {host}:{port}",
+        backend_dir.display()
+    );
     cmd.args([
         "-m",
         "uvicorn",
         "app.main:app",
         "--host",
-        "127.0.0.1",
+        host,
         "--port",
-        "8000",
+        &port.to_string(),
     ])
     .current_dir(backend_dir)
-    // Prefer the project's venv site-packages if available.
-    .env("PYTHONPATH", backend_dir)
-    // Surface backend logs while developing; swap to Stdio::null() for silence.
-    .stdout(Stdio::inherit())
-    .stderr(Stdio::inherit());
+    .env_clear()
+    .envs(backend_env(backend_dir))
+    // Captured (not inherited) so we can forward lines to the frontend. The
+    // lines are not scanned for the uvicorn startup banner.
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped());
     cmd.spawn()
 }
 
-fn launch_backend(proc_ref: SharedChild, backend_dir: PathBuf, app_handle: tauri::AppHandle) {
-    thread::spawn(move || match spawn_backend(&backend_dir) {
-        Ok(child) => {
-            *proc_ref.lock().unwrap() = Some(child);
-            log_backend("Backend started successfully");
+/// Forwards the child's stdout/stderr to the frontend line-by-line via
+/// `backend-log` events. Readiness is tracked separately (by an HTTP probe,
+/// not by scraping these lines) since uvicorn splits its startup
+/// banner across both streams depending on platform and version.
+fn stream_backend_output(child: &mut Child, app_handle: tauri::AppHandle) {
+    if let Some(stdout) = child.stdout.take() {
+        let handle = app_handle.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().flatten() {
+                log::info!("{line}");
+                let _ = handle.emit("backend-log", &line);
+            }
+        });
+    }
+
+    if let Some(stderr) = child.stderr.take() {
+        let handle = app_handle;
+        thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().flatten() {
+                log::info!("{line}");
+                let _ = handle.emit("backend-log", &line);
+            }
+        });
+    }
+}
+
+/// Probes the backend's root endpoint over HTTP until it responds, then emits
+/// `backend-ready` so the frontend can stop showing a loading state and
+/// navigate in. After `READY_PROBE_ATTEMPTS` failed probes it logs a warning
+/// and emits nothing.
+fn probe_backend_ready(app_handle: tauri::AppHandle, host: &str, port: u16) {
+    let request = format!("GET / HTTP/1.0\r\nHost: {host}\r\nConnection: close\r\n\r\n");
+
+    for _ in 0..READY_PROBE_ATTEMPTS {
+        if backend_responded(host, port, &request) {
+            log::info!("Backend readiness probe succeeded");
+            let _ = app_handle.emit("backend-ready", ());
+            return;
+        }
+        thread::sleep(Duration::from_millis(READY_PROBE_INTERVAL_MS));
+    }
+
+    log::warn!("Timed out waiting for backend readiness probe");
+}
+
+/// Sends `request` and reads back the HTTP status line, so "ready" means the
+/// API actually answered rather than just that the socket accepted bytes.
+fn backend_responded(host: &str, port: u16, request: &str) -> bool {
+    let Ok(mut stream) = TcpStream::connect((host, port)) else {
+        return false;
+    };
+    if stream.write_all(request.as_bytes()).is_err() {
+        return false;
+    }
+
+    let mut status_line = String::new();
+    match BufReader::new(stream).read_line(&mut status_line) {
+        Ok(n) if n > 0 => status_line.starts_with("HTTP/"),
+        _ => false,
+    }
+}
+
+/// Supervises a single backend child process: tracks whether the current
+/// exit was requested by us (`manually_killed`) so the monitor thread knows
+/// when a death is a crash worth restarting versus a clean shutdown.
+struct BackendSupervisor {
+    child: Mutex<Option<Child>>,
+    manually_killed: AtomicBool,
+    backend_dir: PathBuf,
+    host: String,
+    port: u16,
+}
+
+type SharedBackend = Arc<BackendSupervisor>;
+
+impl BackendSupervisor {
+    fn new(backend_dir: PathBuf, host: String, port: u16) -> Self {
+        Self {
+            child: Mutex::new(None),
+            manually_killed: AtomicBool::new(false),
+            backend_dir,
+            host,
+            port,
         }
-        Err(err) => {
-            let msg = format!("Failed to start backend: {err}");
-            eprintln!("{msg}");
-            log_backend(&msg);
-            let _ = app_handle.emit("backend-error", err.to_string());
+    }
+
+    fn url(&self) -> String {
+        format!("http://{}:{}", self.host, self.port)
+    }
+
+    fn set_child(&self, child: Child) {
+        *self.child.lock().unwrap() = Some(child);
+    }
+
+    fn kill(&self) {
+        self.manually_killed.store(true, Ordering::SeqCst);
+        if let Some(mut child) = self.child.lock().unwrap().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Polls the supervised child every `POLL_INTERVAL_MS` until it exits, then
+/// reports whether the exit looked unexpected (i.e. not triggered by
+/// `BackendSupervisor::kill`).
+fn monitor_backend(backend: &SharedBackend, app_handle: &tauri::AppHandle) {
+    loop {
+        thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+
+        let status = {
+            let mut guard = backend.child.lock().unwrap();
+            match guard.as_mut() {
+                Some(child) => match child.try_wait() {
+                    Ok(Some(status)) => {
+                        *guard = None;
+                        Some(status)
+                    }
+                    Ok(None) => None,
+                    Err(err) => {
+                        log::warn!("Error polling backend process: {err}");
+                        None
+                    }
+                },
+                None => return,
+            }
+        };
+
+        if let Some(status) = status {
+            if !backend.manually_killed.load(Ordering::SeqCst) {
+                log::error!("Backend exited unexpectedly: {status}");
+                let _ = app_handle.emit("backend-crashed", status.to_string());
+            }
+            return;
+        }
+    }
+}
+
+/// Spawns the backend and keeps it alive: on an unexpected exit it restarts
+/// with exponential backoff, giving up (and emitting `backend-failed`) once
+/// `MAX_RESTART_ATTEMPTS` restarts in a row have also failed. The counter only resets once a
+/// spawn has stayed up past `HEALTHY_UPTIME_SECS`, so a backend that crashes
+/// immediately on every restart still counts toward the ceiling instead of
+/// looping forever at the minimum backoff.
+fn launch_backend(backend: SharedBackend, app_handle: tauri::AppHandle) {
+    thread::spawn(move || {
+        let mut attempt: u32 = 0;
+        loop {
+            match spawn_backend(&backend.backend_dir, &backend.host, backend.port) {
+                Ok(mut child) => {
+                    log::info!("Backend started successfully");
+                    let started_at = Instant::now();
+                    stream_backend_output(&mut child, app_handle.clone());
+                    let probe_handle = app_handle.clone();
+                    let (host, port) = (backend.host.clone(), backend.port);
+                    thread::spawn(move || probe_backend_ready(probe_handle, &host, port));
+                    backend.set_child(child);
+                    monitor_backend(&backend, &app_handle);
+
+                    if started_at.elapsed() >= Duration::from_secs(HEALTHY_UPTIME_SECS) {
+                        attempt = 0;
+                    }
+                }
+                Err(err) => {
+                    log::error!("Failed to start backend: {err}");
+                    let _ = app_handle.emit("backend-error", err.to_string());
+                }
+            }
+
+            if backend.manually_killed.load(Ordering::SeqCst) {
+                break;
+            }
+
+            attempt += 1;
+            if attempt > MAX_RESTART_ATTEMPTS {
+                log::error!("Backend exceeded max restart attempts, giving up");
+                let _ = app_handle.emit("backend-failed", ());
+                break;
+            }
+
+            let backoff_secs = (BASE_BACKOFF_SECS << (attempt - 1)).min(MAX_BACKOFF_SECS);
+            log::info!(
+                "Restarting backend in {backoff_secs}s (attempt {attempt}/{MAX_RESTART_ATTEMPTS})"
+            );
+            if sleep_unless_manually_killed(&backend, Duration::from_secs(backoff_secs)) {
+                break;
+            }
         }
     });
 }
 
-fn terminate_backend(proc_ref: &SharedChild) {
-    if let Some(mut child) = proc_ref.lock().unwrap().take() {
-        let _ = child.kill();
-        let _ = child.wait();
+/// Sleeps for `duration` in short increments so a `kill()` requested mid-wait
+/// is noticed promptly instead of only after the full backoff elapses.
+/// Returns `true` if the backend was manually killed during the wait.
+fn sleep_unless_manually_killed(backend: &SharedBackend, duration: Duration) -> bool {
+    let mut remaining = duration;
+    while remaining > Duration::ZERO {
+        if backend.manually_killed.load(Ordering::SeqCst) {
+            return true;
+        }
+        let step = remaining.min(Duration::from_millis(POLL_INTERVAL_MS));
+        thread::sleep(step);
+        remaining -= step;
     }
+    backend.manually_killed.load(Ordering::SeqCst)
 }
 
 #[tauri::command]
@@ -96,115 +551,151 @@ fn save_scorecard_pdf(path: String, bytes: Vec<u8>) -> Result<(), String> {
     fs::write(path, bytes).map_err(|e| e.to_string())
 }
 
-fn prepare_backend(app: &tauri::App) -> PathBuf {
-    // Prefer a known development absolute path if it exists (useful when running a local bundle).
-    let dev_absolute = PathBuf::from("/Users/steve/dev/web/deadball-web/backend");
-    if dev_absolute.exists() {
-        log_backend("Using dev backend path: /Users/steve/dev/web/deadball-web/backend");
-        return dev_absolute;
+/// Lets the webview ask where the backend actually ended up, instead of
+/// assuming `localhost:8000`.
+#[tauri::command]
+fn get_backend_url(backend: tauri::State<SharedBackend>) -> String {
+    backend.url()
+}
+
+/// The backend source tree, embedded at compile time so the shipped binary
+/// never depends on a separately-bundled resource archive.
+static BACKEND_TEMPLATE: Dir = include_dir!("$CARGO_MANIFEST_DIR/../backend");
+
+/// Tied to the crate version so a stamp mismatch means "this backend
+/// predates the currently-running frontend" rather than tracking a
+/// separate, easy-to-forget counter.
+const BACKEND_VERSION: &str = env!("CARGO_PKG_VERSION");
+const BACKEND_VERSION_STAMP: &str = "BACKEND_VERSION";
+const BACKEND_USER_DATA_DIR: &str = "data";
+
+fn stamped_backend_version(app_data_dir: &Path) -> Option<String> {
+    fs::read_to_string(app_data_dir.join(BACKEND_VERSION_STAMP))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+/// Re-materializes the embedded backend template into `app_data_backend`,
+/// preserving the user-writable `data/` subdirectory across the rewrite, and
+/// writes the `BACKEND_VERSION` stamp alongside it.
+fn materialize_backend(app_data_backend: &Path) -> std::io::Result<()> {
+    let data_dir = app_data_backend.join(BACKEND_USER_DATA_DIR);
+    let preserved_data_dir = app_data_backend.with_file_name("backend-data.preserve");
+    if data_dir.exists() {
+        let _ = fs::remove_dir_all(&preserved_data_dir);
+        fs::rename(&data_dir, &preserved_data_dir)?;
+    }
+
+    if app_data_backend.exists() {
+        fs::remove_dir_all(app_data_backend)?;
+    }
+    BACKEND_TEMPLATE
+        .extract(app_data_backend)
+        .map_err(std::io::Error::other)?;
+
+    if preserved_data_dir.exists() {
+        // The freshly-extracted template may itself ship a `data/` dir, which
+        // would make this a rename onto a non-empty directory and fail.
+        if data_dir.exists() {
+            fs::remove_dir_all(&data_dir)?;
+        }
+        fs::rename(&preserved_data_dir, &data_dir)?;
     }
 
-    // Choose an app data location for a writable backend copy.
-    let app_data_backend = app
+    let stamp_path = app_data_backend
+        .parent()
+        .unwrap_or(app_data_backend)
+        .join(BACKEND_VERSION_STAMP);
+    fs::write(stamp_path, BACKEND_VERSION)
+}
+
+fn prepare_backend(app: &tauri::App) -> PathBuf {
+    let app_data_dir = app
         .path()
         .app_data_dir()
-        .unwrap_or_else(|_| PathBuf::from("backend"))
-        .join("backend");
+        .unwrap_or_else(|_| PathBuf::from("."));
+    let app_data_backend = app_data_dir.join("backend");
 
-    if app_data_backend.exists() {
-        log_backend(&format!(
-            "Using existing app data backend: {}",
+    let up_to_date = app_data_backend.exists()
+        && stamped_backend_version(&app_data_dir).as_deref() == Some(BACKEND_VERSION);
+
+    if up_to_date {
+        log::info!(
+            "Backend at {} already matches version {BACKEND_VERSION}",
             app_data_backend.display()
-        ));
-        return app_data_backend;
-    }
-
-    // Extract from bundled resources/backend-template.tar.gz into app data.
-    if let Ok(res_dir) = app.path().resource_dir() {
-        let archive = res_dir.join("backend-template.tar.gz");
-        if archive.exists() {
-            let _ = fs::create_dir_all(app_data_backend.parent().unwrap_or(&app_data_backend));
-            let status = Command::new("tar")
-                .args([
-                    "-xzf",
-                    archive
-                        .to_str()
-                        .unwrap_or("backend-template.tar.gz"),
-                    "-C",
-                    app_data_backend
-                        .parent()
-                        .unwrap_or(&app_data_backend)
-                        .to_str()
-                        .unwrap_or("."),
-                ])
-                .status();
-            match status {
-                Ok(s) if s.success() => {
-                    log_backend(&format!(
-                        "Extracted backend template to app data: {}",
-                        app_data_backend.display()
-                    ));
-                    return app_data_backend;
-                }
-                Ok(s) => log_backend(&format!("tar exited with status: {}", s)),
-                Err(err) => log_backend(&format!("Failed to run tar: {}", err)),
-            }
-        } else {
-            log_backend("No backend-template found in resources");
-        }
+        );
     } else {
-        log_backend("No resource dir available");
-    }
-
-    // Fallback to dev-relative path (repo layout).
-    if let Ok(exe_path) = env::current_exe() {
-        if let Some(parent) = exe_path.parent() {
-            let dev = parent.join("../backend");
-            if dev.exists() {
-                log_backend(&format!(
-                    "Using dev-relative backend path next to executable: {}",
-                    dev.display()
-                ));
-                return dev;
-            }
+        log::info!(
+            "Materializing backend template version {BACKEND_VERSION} into {}",
+            app_data_backend.display()
+        );
+        if let Err(err) = materialize_backend(&app_data_backend) {
+            log::error!("Failed to materialize backend template: {err}");
+            let _ = app.handle().emit("backend-error", err.to_string());
         }
     }
 
-    log_backend("Falling back to ../backend");
-    PathBuf::from("../backend")
+    app_data_backend
 }
 
 fn main() {
-    let backend_proc: SharedChild = Arc::new(Mutex::new(None));
+    let backend_slot: Arc<Mutex<Option<SharedBackend>>> = Arc::new(Mutex::new(None));
 
     let app = tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
-        .invoke_handler(tauri::generate_handler![save_scorecard_pdf])
+        .invoke_handler(tauri::generate_handler![
+            save_scorecard_pdf,
+            get_backend_url
+        ])
         .setup({
-            let backend_proc = backend_proc.clone();
+            let backend_slot = backend_slot.clone();
             move |app| {
+                init_logging(app);
+
+                let log_dir = app.path().app_log_dir().unwrap_or_else(|_| env::temp_dir());
+                if let Some(crash_path) = find_previous_crash(&log_dir) {
+                    log::warn!(
+                        "Found crash report from previous run: {}",
+                        crash_path.display()
+                    );
+                    let _ = app
+                        .handle()
+                        .emit("previous-crash", crash_path.to_string_lossy().to_string());
+                }
+                install_panic_hook(log_dir, backend_slot.clone());
+
                 let backend_path = prepare_backend(app);
-                launch_backend(backend_proc.clone(), backend_path, app.handle().clone());
+                let (host, port) = resolve_backend_address();
+                let backend = Arc::new(BackendSupervisor::new(backend_path, host, port));
+                launch_backend(backend.clone(), app.handle().clone());
+                app.manage(backend.clone());
+                *backend_slot.lock().unwrap() = Some(backend);
                 Ok(())
             }
         })
         .on_window_event({
-            let backend_proc = backend_proc.clone();
+            let backend_slot = backend_slot.clone();
             move |_window, event| {
                 if let WindowEvent::CloseRequested { .. } = event {
-                    terminate_backend(&backend_proc);
+                    if let Some(backend) = backend_slot.lock().unwrap().as_ref() {
+                        backend.kill();
+                    }
                 }
             }
         })
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
 
-    let backend_proc_for_run = backend_proc.clone();
+    let backend_slot_for_run = backend_slot.clone();
     app.run(move |_app_handle, event| {
         if matches!(event, RunEvent::ExitRequested { .. } | RunEvent::Exit) {
-            terminate_backend(&backend_proc_for_run);
+            if let Some(backend) = backend_slot_for_run.lock().unwrap().as_ref() {
+                backend.kill();
+            }
         }
     });
 
-    terminate_backend(&backend_proc);
+    if let Some(backend) = backend_slot.lock().unwrap().as_ref() {
+        backend.kill();
+    }
 }